@@ -0,0 +1,304 @@
+// Undo rename from Cargo.toml
+extern crate serde_crate as serde;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::JsOption;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "serde")]
+enum Repr<T> {
+    Undefined,
+    Null,
+    Some(T),
+}
+
+impl<T> From<Repr<T>> for JsOption<T> {
+    fn from(repr: Repr<T>) -> Self {
+        match repr {
+            Repr::Some(val) => Self::Some(val),
+            Repr::Null => Self::Null,
+            Repr::Undefined => Self::Undefined,
+        }
+    }
+}
+
+/// Serializes a `JsOption<T>` as an externally-tagged enum with `Undefined`,
+/// `Null` and `Some` variants, instead of collapsing it onto the regular
+/// `Option` encoding used by [`JsOption`]'s own `Serialize` impl.
+///
+/// Unlike the default impl, this preserves all three states losslessly, so
+/// it round-trips through formats without a JSON-style null/missing
+/// distinction (bincode, MessagePack, postcard, ...).
+pub fn serialize<T, S>(value: &JsOption<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    match value {
+        JsOption::Undefined => Repr::<&T>::Undefined,
+        JsOption::Null => Repr::<&T>::Null,
+        JsOption::Some(val) => Repr::Some(val),
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes a `JsOption<T>` from the externally-tagged enum produced by
+/// [`serialize`].
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<JsOption<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Repr::deserialize(deserializer).map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use super::serde;
+    use serde::{
+        de::{self, EnumAccess, IntoDeserializer, VariantAccess, Visitor},
+        ser::Impossible,
+    };
+
+    use super::{deserialize, serialize};
+    use crate::JsOption;
+
+    #[derive(Debug)]
+    struct Error(String);
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::error::Error for Error {}
+
+    impl de::Error for Error {
+        fn custom<T: fmt::Display>(msg: T) -> Self {
+            Error(msg.to_string())
+        }
+    }
+
+    impl serde::ser::Error for Error {
+        fn custom<T: fmt::Display>(msg: T) -> Self {
+            Error(msg.to_string())
+        }
+    }
+
+    /// A minimal index-tagged wire value: `(variant_index, payload)`. Stands
+    /// in for formats like bincode/postcard, which address enum variants by
+    /// position rather than by name, to make sure `serialize` and
+    /// `deserialize` agree on variant order.
+    #[derive(Debug, PartialEq)]
+    struct IndexedVariant {
+        index: u32,
+        payload: Option<i32>,
+    }
+
+    struct IndexSerializer;
+
+    macro_rules! unreachable_ser_methods {
+        () => {
+            fn serialize_bool(self, _v: bool) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_i8(self, _v: i8) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_i16(self, _v: i16) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_i32(self, _v: i32) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_i64(self, _v: i64) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_u8(self, _v: u8) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_u16(self, _v: u16) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_u32(self, _v: u32) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_u64(self, _v: u64) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_f32(self, _v: f32) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_f64(self, _v: f64) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_char(self, _v: char) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_str(self, _v: &str) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_bytes(self, _v: &[u8]) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_none(self) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_some<T: ?Sized + serde::Serialize>(self, _v: &T) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_unit(self) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_unit_struct(self, _n: &'static str) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(self, _n: &'static str, _v: &T) -> Result<IndexedVariant, Error> { unreachable!() }
+            fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> { unreachable!() }
+            fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> { unreachable!() }
+            fn serialize_tuple_struct(self, _n: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Error> { unreachable!() }
+            fn serialize_tuple_variant(self, _n: &'static str, _i: u32, _v: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Error> { unreachable!() }
+            fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> { unreachable!() }
+            fn serialize_struct(self, _n: &'static str, _len: usize) -> Result<Self::SerializeStruct, Error> { unreachable!() }
+            fn serialize_struct_variant(self, _n: &'static str, _i: u32, _v: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Error> { unreachable!() }
+        };
+    }
+
+    impl serde::Serializer for IndexSerializer {
+        type Ok = IndexedVariant;
+        type Error = Error;
+        type SerializeSeq = Impossible<IndexedVariant, Error>;
+        type SerializeTuple = Impossible<IndexedVariant, Error>;
+        type SerializeTupleStruct = Impossible<IndexedVariant, Error>;
+        type SerializeTupleVariant = Impossible<IndexedVariant, Error>;
+        type SerializeMap = Impossible<IndexedVariant, Error>;
+        type SerializeStruct = Impossible<IndexedVariant, Error>;
+        type SerializeStructVariant = Impossible<IndexedVariant, Error>;
+
+        fn serialize_unit_variant(
+            self,
+            _name: &'static str,
+            variant_index: u32,
+            _variant: &'static str,
+        ) -> Result<IndexedVariant, Error> {
+            Ok(IndexedVariant { index: variant_index, payload: None })
+        }
+
+        fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+            self,
+            _name: &'static str,
+            variant_index: u32,
+            _variant: &'static str,
+            value: &T,
+        ) -> Result<IndexedVariant, Error> {
+            let payload = value.serialize(I32Serializer)?;
+            Ok(IndexedVariant { index: variant_index, payload: Some(payload) })
+        }
+
+        unreachable_ser_methods!();
+    }
+
+    struct I32Serializer;
+
+    impl serde::Serializer for I32Serializer {
+        type Ok = i32;
+        type Error = Error;
+        type SerializeSeq = Impossible<i32, Error>;
+        type SerializeTuple = Impossible<i32, Error>;
+        type SerializeTupleStruct = Impossible<i32, Error>;
+        type SerializeTupleVariant = Impossible<i32, Error>;
+        type SerializeMap = Impossible<i32, Error>;
+        type SerializeStruct = Impossible<i32, Error>;
+        type SerializeStructVariant = Impossible<i32, Error>;
+
+        fn serialize_i32(self, v: i32) -> Result<i32, Error> {
+            Ok(v)
+        }
+
+        fn serialize_bool(self, _v: bool) -> Result<i32, Error> { unreachable!() }
+        fn serialize_i8(self, _v: i8) -> Result<i32, Error> { unreachable!() }
+        fn serialize_i16(self, _v: i16) -> Result<i32, Error> { unreachable!() }
+        fn serialize_i64(self, _v: i64) -> Result<i32, Error> { unreachable!() }
+        fn serialize_u8(self, _v: u8) -> Result<i32, Error> { unreachable!() }
+        fn serialize_u16(self, _v: u16) -> Result<i32, Error> { unreachable!() }
+        fn serialize_u32(self, _v: u32) -> Result<i32, Error> { unreachable!() }
+        fn serialize_u64(self, _v: u64) -> Result<i32, Error> { unreachable!() }
+        fn serialize_f32(self, _v: f32) -> Result<i32, Error> { unreachable!() }
+        fn serialize_f64(self, _v: f64) -> Result<i32, Error> { unreachable!() }
+        fn serialize_char(self, _v: char) -> Result<i32, Error> { unreachable!() }
+        fn serialize_str(self, _v: &str) -> Result<i32, Error> { unreachable!() }
+        fn serialize_bytes(self, _v: &[u8]) -> Result<i32, Error> { unreachable!() }
+        fn serialize_none(self) -> Result<i32, Error> { unreachable!() }
+        fn serialize_some<T: ?Sized + serde::Serialize>(self, _v: &T) -> Result<i32, Error> { unreachable!() }
+        fn serialize_unit(self) -> Result<i32, Error> { unreachable!() }
+        fn serialize_unit_struct(self, _n: &'static str) -> Result<i32, Error> { unreachable!() }
+        fn serialize_unit_variant(self, _n: &'static str, _i: u32, _v: &'static str) -> Result<i32, Error> { unreachable!() }
+        fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(self, _n: &'static str, _v: &T) -> Result<i32, Error> { unreachable!() }
+        fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(self, _n: &'static str, _i: u32, _v: &'static str, _value: &T) -> Result<i32, Error> { unreachable!() }
+        fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> { unreachable!() }
+        fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> { unreachable!() }
+        fn serialize_tuple_struct(self, _n: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Error> { unreachable!() }
+        fn serialize_tuple_variant(self, _n: &'static str, _i: u32, _v: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Error> { unreachable!() }
+        fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> { unreachable!() }
+        fn serialize_struct(self, _n: &'static str, _len: usize) -> Result<Self::SerializeStruct, Error> { unreachable!() }
+        fn serialize_struct_variant(self, _n: &'static str, _i: u32, _v: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Error> { unreachable!() }
+    }
+
+    impl<'de> de::Deserializer<'de> for IndexedVariant {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+            unreachable!()
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            _variants: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            visitor.visit_enum(self)
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct identifier ignored_any
+        }
+    }
+
+    impl<'de> EnumAccess<'de> for IndexedVariant {
+        type Error = Error;
+        type Variant = Self;
+
+        fn variant_seed<V: de::DeserializeSeed<'de>>(
+            self,
+            seed: V,
+        ) -> Result<(V::Value, Self), Error> {
+            let index = self.index;
+            let value = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(index))?;
+            Ok((value, self))
+        }
+    }
+
+    impl<'de> VariantAccess<'de> for IndexedVariant {
+        type Error = Error;
+
+        fn unit_variant(self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+            let payload = self.payload.expect("newtype variant without a payload");
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(payload))
+        }
+
+        fn tuple_variant<V: Visitor<'de>>(self, _len: usize, _visitor: V) -> Result<V::Value, Error> {
+            unreachable!()
+        }
+
+        fn struct_variant<V: Visitor<'de>>(
+            self,
+            _fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Error> {
+            unreachable!()
+        }
+    }
+
+    fn round_trip(value: JsOption<i32>) -> JsOption<i32> {
+        let wire = serialize(&value, IndexSerializer).unwrap();
+        deserialize(wire).unwrap()
+    }
+
+    #[test]
+    fn round_trips_all_three_variants_through_the_numeric_index_path() {
+        assert_eq!(round_trip(JsOption::Undefined), JsOption::Undefined);
+        assert_eq!(round_trip(JsOption::Null), JsOption::Null);
+        assert_eq!(round_trip(JsOption::Some(42)), JsOption::Some(42));
+    }
+
+    #[test]
+    fn variant_indices_match_declaration_order() {
+        assert_eq!(
+            serialize(&JsOption::<i32>::Undefined, IndexSerializer).unwrap(),
+            IndexedVariant { index: 0, payload: None }
+        );
+        assert_eq!(
+            serialize(&JsOption::<i32>::Null, IndexSerializer).unwrap(),
+            IndexedVariant { index: 1, payload: None }
+        );
+        assert_eq!(
+            serialize(&JsOption::Some(7), IndexSerializer).unwrap(),
+            IndexedVariant { index: 2, payload: Some(7) }
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use async_graphql::{
+    parser::types::Field, registry, ContextSelectionSet, InputType, InputValueError,
+    InputValueResult, OutputType, Positioned, ServerResult, Value,
+};
+
+use crate::JsOption;
+
+impl<T: InputType> InputType for JsOption<T> {
+    type RawValueType = T::RawValueType;
+
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    /// `JsOption<T>` is nullable, unlike a required `T`, so this strips the
+    /// `!` suffix the default impl would otherwise inherit from `type_name`.
+    fn qualified_type_name() -> String {
+        Self::type_name().to_string()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        Self::qualified_type_name()
+    }
+
+    /// Parses a GraphQL argument. An omitted argument becomes `Undefined`, an
+    /// explicit `null` becomes `Null`, and any other value is delegated to
+    /// `T::parse`.
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        match value {
+            None => Ok(Self::Undefined),
+            Some(Value::Null) => Ok(Self::Null),
+            Some(value) => {
+                T::parse(Some(value)).map(Self::Some).map_err(InputValueError::propagate)
+            }
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Self::Some(val) => val.to_value(),
+            Self::Null | Self::Undefined => Value::Null,
+        }
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        match self {
+            Self::Some(val) => val.as_raw_value(),
+            Self::Null | Self::Undefined => None,
+        }
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl<T: OutputType> OutputType for JsOption<T> {
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    /// `JsOption<T>` is nullable, unlike a required `T`, so this strips the
+    /// `!` suffix the default impl would otherwise inherit from `type_name`.
+    fn qualified_type_name() -> String {
+        Self::type_name().to_string()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        Self::qualified_type_name()
+    }
+
+    /// Resolves to `null` for `Null` and `Undefined`, and delegates to
+    /// `T::resolve` for `Some`.
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        match self {
+            Self::Some(val) => OutputType::resolve(val, ctx, field).await,
+            Self::Null | Self::Undefined => Ok(Value::Null),
+        }
+    }
+}
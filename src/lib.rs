@@ -35,6 +35,72 @@ use std::ops::{Deref, DerefMut};
 #[cfg(feature = "serde")]
 mod serde;
 
+// Implements async-graphql's `InputType`/`OutputType` for `JsOption<T>`: a
+// GraphQL `null` parses to `Null`, an omitted argument to `Undefined`, and a
+// present value to `Some`. Plays the same role as async-graphql's own
+// `MaybeUndefined<T>`.
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+
+/// Implements `zvariant::Type` for `JsOption<T>` so it can appear in zbus
+/// message structs over D-Bus, plus (de)serialization mirroring
+/// `zvariant::Optional<T>`'s sentinel convention, for use with
+/// `#[serde(with = "js_option::zvariant")]`. `Undefined` is rejected on
+/// serialization, and a `Some` holding `T`'s sentinel value reads back as
+/// `Null`.
+///
+/// ```
+/// # extern crate serde_crate as serde;
+/// use js_option::JsOption;
+/// use serde::{Deserialize, Serialize};
+/// use zvariant::Type;
+///
+/// #[derive(Serialize, Deserialize, Type)]
+/// # #[serde(crate = "serde")]
+/// struct MyStruct {
+///     #[serde(with = "js_option::zvariant")]
+///     my_field: JsOption<String>,
+/// }
+/// ```
+#[cfg(feature = "zvariant")]
+pub mod zvariant;
+
+/// Lossless three-state (de)serialization as an externally-tagged enum, for
+/// use with `#[serde(with = "js_option::as_enum")]`.
+///
+/// ```
+/// # extern crate serde_crate as serde;
+/// use js_option::JsOption;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// # #[serde(crate = "serde")]
+/// struct MyStruct {
+///     #[serde(default, with = "js_option::as_enum")]
+///     my_field: JsOption<String>,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod as_enum;
+
+/// Three-state (de)serialization for a plain `Option<Option<T>>` field, for
+/// use with `#[serde(default, with = "js_option::nested_option")]` when
+/// migrating a struct to `JsOption` isn't an option.
+///
+/// ```
+/// # extern crate serde_crate as serde;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// # #[serde(crate = "serde")]
+/// struct MyStruct {
+///     #[serde(default, with = "js_option::nested_option", skip_serializing_if = "Option::is_none")]
+///     my_field: Option<Option<String>>,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod nested_option;
+
 /// An `Option`-like type with two data-less variants in addition to `Some`:
 /// `Null` and `Undefined`.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -88,6 +154,29 @@ impl<T> JsOption<T> {
         }
     }
 
+    /// Applies this `JsOption` as a PATCH-style update onto `target`.
+    ///
+    /// `Self::Some(v)` sets `target` to `Some(v)`, `Self::Null` clears it to
+    /// `None`, and `Self::Undefined` leaves `target` unchanged. This is the
+    /// common semantics for a deserialized patch field: present means set,
+    /// explicit `null` means clear, and a missing field means don't touch.
+    pub fn apply_to(self, target: &mut Option<T>) {
+        match self {
+            Self::Some(val) => *target = Some(val),
+            Self::Null => *target = None,
+            Self::Undefined => {}
+        }
+    }
+
+    /// Applies this `JsOption` as a PATCH-style update onto `base`,
+    /// returning the result.
+    ///
+    /// See [`apply_to`](Self::apply_to) for the exact semantics.
+    pub fn merge(self, mut base: Option<T>) -> Option<T> {
+        self.apply_to(&mut base);
+        base
+    }
+
     /// Returns `true` if the `JsOption` contains a value.
     pub const fn is_some(&self) -> bool {
         matches!(self, Self::Some(_))
@@ -160,6 +249,199 @@ impl<T> JsOption<T> {
             Self::Undefined => JsOption::Undefined,
         }
     }
+
+    /// Returns `Undefined` if the `JsOption` is `Undefined`, `Null` if it is
+    /// `Null`, and otherwise returns `other`.
+    pub fn and<U>(self, other: JsOption<U>) -> JsOption<U> {
+        match self {
+            Self::Some(_) => other,
+            Self::Null => JsOption::Null,
+            Self::Undefined => JsOption::Undefined,
+        }
+    }
+
+    /// Returns `Undefined` if the `JsOption` is `Undefined`, `Null` if it is
+    /// `Null`, and otherwise calls `f` with the wrapped value and returns the
+    /// result.
+    pub fn and_then<U, F: FnOnce(T) -> JsOption<U>>(self, f: F) -> JsOption<U> {
+        match self {
+            Self::Some(val) => f(val),
+            Self::Null => JsOption::Null,
+            Self::Undefined => JsOption::Undefined,
+        }
+    }
+
+    /// Returns the more defined of `self` and `other`, treating `Some` as
+    /// more defined than `Null`, and `Null` as more defined than `Undefined`.
+    ///
+    /// If `self` is `Some`, it is always returned without looking at `other`,
+    /// matching `Option::or`. Otherwise the variant that carries more
+    /// information wins, so e.g. `Undefined.or(Null)` is `Null`: an explicit
+    /// absence is more informative than an implicit one.
+    pub fn or(self, other: Self) -> Self {
+        if other.defined_rank() > self.defined_rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the `JsOption` if it is `Some`, otherwise calls `f` and
+    /// returns the more defined of `self` and the result, per the same rule
+    /// as [`or`](Self::or).
+    pub fn or_else<F: FnOnce() -> Self>(self, f: F) -> Self {
+        if self.is_some() {
+            self
+        } else {
+            self.or(f())
+        }
+    }
+
+    /// Returns `Some(t)` if `self` is `Some(t)` and `predicate(&t)` is
+    /// `true`; returns `Null` if `self` is `Some(t)` but the predicate
+    /// rejects it (the value is explicitly discarded); otherwise returns
+    /// `self` unchanged (`Null` and `Undefined` pass through as-is).
+    pub fn filter<P: FnOnce(&T) -> bool>(self, predicate: P) -> Self {
+        match self {
+            Self::Some(val) if predicate(&val) => Self::Some(val),
+            Self::Some(_) => Self::Null,
+            other => other,
+        }
+    }
+
+    /// Returns whichever of `self` and `other` contains a value, if exactly
+    /// one of them does; returns `Undefined` if both or neither do.
+    pub fn xor(self, other: Self) -> Self {
+        match (self.is_some(), other.is_some()) {
+            (true, false) => self,
+            (false, true) => other,
+            _ => Self::Undefined,
+        }
+    }
+
+    /// Returns the contained value, or inserts and returns `value` if the
+    /// `JsOption` is `Null` or `Undefined`.
+    pub fn get_or_insert(&mut self, value: T) -> &mut T {
+        self.get_or_insert_with(|| value)
+    }
+
+    /// Returns the contained value, or inserts and returns the result of `f`
+    /// if the `JsOption` is `Null` or `Undefined`.
+    pub fn get_or_insert_with<F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        if !self.is_some() {
+            *self = Self::Some(f());
+        }
+
+        match self {
+            Self::Some(val) => val,
+            Self::Null | Self::Undefined => unreachable!(),
+        }
+    }
+
+    /// Takes the value out of the `JsOption`, leaving `Undefined` in its
+    /// place.
+    pub fn take(&mut self) -> Option<T> {
+        std::mem::replace(self, Self::Undefined).into_option()
+    }
+
+    /// Replaces the value in the `JsOption` with `value`, returning the old
+    /// `JsOption`.
+    pub fn replace(&mut self, value: T) -> Self {
+        std::mem::replace(self, Self::Some(value))
+    }
+
+    /// Zips `self` with another `JsOption`.
+    ///
+    /// If `self` is `Some(a)` and `other` is `Some(b)`, returns
+    /// `Some((a, b))`. Otherwise returns `Null` if either side carried a
+    /// value or was explicitly `Null`, and `Undefined` only if both sides
+    /// were `Undefined` — the pair can't be formed, but the more defined
+    /// reason why is preserved.
+    pub fn zip<U>(self, other: JsOption<U>) -> JsOption<(T, U)> {
+        match (self, other) {
+            (Self::Some(a), JsOption::Some(b)) => JsOption::Some((a, b)),
+            (a, b) => {
+                if a.defined_rank().max(b.defined_rank()) == 0 {
+                    JsOption::Undefined
+                } else {
+                    JsOption::Null
+                }
+            }
+        }
+    }
+
+    /// Transforms the `JsOption<T>` into a `Result<T, E>`, mapping `Some(v)`
+    /// to `Ok(v)` and both `Null` and `Undefined` to `Err(err)`.
+    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+        match self {
+            Self::Some(val) => Ok(val),
+            Self::Null | Self::Undefined => Err(err),
+        }
+    }
+
+    /// Transforms the `JsOption<T>` into a `Result<T, E>`, mapping `Some(v)`
+    /// to `Ok(v)` and both `Null` and `Undefined` to `Err(err())`.
+    pub fn ok_or_else<E, F: FnOnce() -> E>(self, err: F) -> Result<T, E> {
+        match self {
+            Self::Some(val) => Ok(val),
+            Self::Null | Self::Undefined => Err(err()),
+        }
+    }
+
+    /// Returns an iterator over the possibly contained value.
+    ///
+    /// The iterator yields one value if the `JsOption` is `Some`, otherwise
+    /// none.
+    pub fn iter(&self) -> JsOptionIter<&T> {
+        JsOptionIter { inner: self.as_ref().into_option() }
+    }
+
+    /// Returns a mutable iterator over the possibly contained value.
+    ///
+    /// The iterator yields one value if the `JsOption` is `Some`, otherwise
+    /// none.
+    pub fn iter_mut(&mut self) -> JsOptionIter<&mut T> {
+        JsOptionIter { inner: self.as_mut().into_option() }
+    }
+
+    /// Returns the contained `Some` value, consuming `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the given `msg` if the value equals `Null` or
+    /// `Undefined`.
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        match self {
+            Self::Some(val) => val,
+            Self::Null | Self::Undefined => panic!("{}", msg),
+        }
+    }
+
+    /// Ranks how much information the current variant carries: `Undefined`
+    /// is least defined, `Null` is more defined than `Undefined`, and `Some`
+    /// is most defined. Used to implement [`or`](Self::or), [`or_else`](Self::or_else)
+    /// and [`zip`](Self::zip).
+    const fn defined_rank(&self) -> u8 {
+        match self {
+            Self::Undefined => 0,
+            Self::Null => 1,
+            Self::Some(_) => 2,
+        }
+    }
+}
+
+impl<T> JsOption<JsOption<T>> {
+    /// Converts from `JsOption<JsOption<T>>` to `JsOption<T>`, flattening one
+    /// level of nesting: an outer `Some` returns the inner `JsOption` as-is,
+    /// and an outer `Null` or `Undefined` is kept.
+    pub fn flatten(self) -> JsOption<T> {
+        match self {
+            Self::Some(inner) => inner,
+            Self::Null => JsOption::Null,
+            Self::Undefined => JsOption::Undefined,
+        }
+    }
 }
 
 impl<T: Default> JsOption<T> {
@@ -189,3 +471,251 @@ impl<T> Default for JsOption<T> {
         Self::Undefined
     }
 }
+
+impl<T> IntoIterator for JsOption<T> {
+    type Item = T;
+    type IntoIter = JsOptionIter<T>;
+
+    /// Returns a consuming iterator over the possibly contained value.
+    ///
+    /// The iterator yields one value if the `JsOption` is `Some`, otherwise
+    /// none.
+    fn into_iter(self) -> JsOptionIter<T> {
+        JsOptionIter { inner: self.into_option() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a JsOption<T> {
+    type Item = &'a T;
+    type IntoIter = JsOptionIter<&'a T>;
+
+    fn into_iter(self) -> JsOptionIter<&'a T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut JsOption<T> {
+    type Item = &'a mut T;
+    type IntoIter = JsOptionIter<&'a mut T>;
+
+    fn into_iter(self) -> JsOptionIter<&'a mut T> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the possibly contained value of a [`JsOption`].
+///
+/// This is the iterator returned by [`JsOption::iter`], [`JsOption::iter_mut`]
+/// and [`JsOption::into_iter`]. It yields the contained value if the
+/// `JsOption` is `Some`, and nothing for `Null` or `Undefined`.
+#[derive(Clone, Debug)]
+pub struct JsOptionIter<T> {
+    inner: Option<T>,
+}
+
+impl<T> Iterator for JsOptionIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.iter().size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for JsOptionIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.take()
+    }
+}
+
+impl<T> ExactSizeIterator for JsOptionIter<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::JsOption::{Null, Some as MaybeSome, Undefined};
+
+    #[test]
+    fn or_picks_the_more_defined_side() {
+        assert_eq!(Undefined::<i32>.or(Undefined), Undefined);
+        assert_eq!(Undefined::<i32>.or(Null), Null);
+        assert_eq!(Null::<i32>.or(Undefined), Null);
+        assert_eq!(Null::<i32>.or(Null), Null);
+        assert_eq!(Undefined.or(MaybeSome(1)), MaybeSome(1));
+        assert_eq!(Null.or(MaybeSome(1)), MaybeSome(1));
+        // `self` wins whenever it's `Some`, regardless of `other`.
+        assert_eq!(MaybeSome(1).or(MaybeSome(2)), MaybeSome(1));
+        assert_eq!(MaybeSome(1).or(Null), MaybeSome(1));
+        assert_eq!(MaybeSome(1).or(Undefined), MaybeSome(1));
+    }
+
+    #[test]
+    fn xor_collapses_to_undefined_unless_exactly_one_side_has_a_value() {
+        assert_eq!(MaybeSome(1).xor(Undefined), MaybeSome(1));
+        assert_eq!(Undefined.xor(MaybeSome(1)), MaybeSome(1));
+        assert_eq!(MaybeSome(1).xor(Null), MaybeSome(1));
+        assert_eq!(Null.xor(MaybeSome(1)), MaybeSome(1));
+        assert_eq!(MaybeSome(1).xor(MaybeSome(2)), Undefined);
+        assert_eq!(Undefined::<i32>.xor(Null), Undefined);
+        assert_eq!(Null::<i32>.xor(Undefined), Undefined);
+        assert_eq!(Null::<i32>.xor(Null), Undefined);
+        assert_eq!(Undefined::<i32>.xor(Undefined), Undefined);
+    }
+
+    #[test]
+    fn zip_pairs_values_and_otherwise_keeps_the_more_defined_reason() {
+        assert_eq!(MaybeSome(1).zip(MaybeSome("a")), MaybeSome((1, "a")));
+        assert_eq!(MaybeSome(1).zip(Undefined::<&str>), Null);
+        assert_eq!(Undefined::<i32>.zip(MaybeSome("a")), Null);
+        assert_eq!(MaybeSome(1).zip(Null::<&str>), Null);
+        assert_eq!(Null::<i32>.zip(MaybeSome("a")), Null);
+        assert_eq!(Null::<i32>.zip(Undefined::<&str>), Null);
+        assert_eq!(Undefined::<i32>.zip(Null::<&str>), Null);
+        assert_eq!(Undefined::<i32>.zip(Undefined::<&str>), Undefined);
+    }
+
+    #[test]
+    fn and_short_circuits_on_null_and_undefined_but_not_some() {
+        assert_eq!(MaybeSome(1).and(MaybeSome("a")), MaybeSome("a"));
+        assert_eq!(Null::<i32>.and(MaybeSome("a")), Null);
+        assert_eq!(Undefined::<i32>.and(MaybeSome("a")), Undefined);
+        // The other side's variant is irrelevant once `self` isn't `Some`.
+        assert_eq!(Null::<i32>.and(Undefined::<&str>), Null);
+        assert_eq!(Undefined::<i32>.and(Null::<&str>), Undefined);
+    }
+
+    #[test]
+    fn and_then_preserves_null_vs_undefined_without_calling_f() {
+        let f = |v: i32| MaybeSome(v * 2);
+        assert_eq!(MaybeSome(1).and_then(f), MaybeSome(2));
+        assert_eq!(Null::<i32>.and_then(f), Null);
+        assert_eq!(Undefined::<i32>.and_then(f), Undefined);
+    }
+
+    #[test]
+    fn filter_turns_a_rejected_some_into_null() {
+        assert_eq!(MaybeSome(4).filter(|v| v % 2 == 0), MaybeSome(4));
+        assert_eq!(MaybeSome(3).filter(|v| v % 2 == 0), Null);
+        // `Null` and `Undefined` pass through unchanged without calling the
+        // predicate.
+        assert_eq!(Null::<i32>.filter(|_| panic!("predicate should not run")), Null);
+        assert_eq!(Undefined::<i32>.filter(|_| panic!("predicate should not run")), Undefined);
+    }
+
+    #[test]
+    fn get_or_insert_only_inserts_when_not_some() {
+        let mut some = MaybeSome(1);
+        assert_eq!(*some.get_or_insert(2), 1);
+        assert_eq!(some, MaybeSome(1));
+
+        let mut null = Null::<i32>;
+        assert_eq!(*null.get_or_insert(2), 2);
+        assert_eq!(null, MaybeSome(2));
+
+        let mut undefined = Undefined::<i32>;
+        assert_eq!(*undefined.get_or_insert_with(|| 3), 3);
+        assert_eq!(undefined, MaybeSome(3));
+    }
+
+    #[test]
+    fn take_replaces_self_with_undefined_and_returns_the_old_value() {
+        let mut some = MaybeSome(1);
+        assert_eq!(some.take(), Some(1));
+        assert_eq!(some, Undefined);
+
+        let mut null = Null::<i32>;
+        assert_eq!(null.take(), None);
+        assert_eq!(null, Undefined);
+    }
+
+    #[test]
+    fn replace_overwrites_self_and_returns_the_old_jsoption() {
+        let mut some = MaybeSome(1);
+        assert_eq!(some.replace(2), MaybeSome(1));
+        assert_eq!(some, MaybeSome(2));
+
+        let mut undefined = Undefined::<i32>;
+        assert_eq!(undefined.replace(1), Undefined);
+        assert_eq!(undefined, MaybeSome(1));
+    }
+
+    #[test]
+    fn flatten_returns_the_inner_value_or_keeps_the_outer_null_or_undefined() {
+        assert_eq!(MaybeSome(MaybeSome(1)).flatten(), MaybeSome(1));
+        assert_eq!(MaybeSome(Null::<i32>).flatten(), Null);
+        assert_eq!(MaybeSome(Undefined::<i32>).flatten(), Undefined);
+        assert_eq!(Null::<super::JsOption<i32>>.flatten(), Null);
+        assert_eq!(Undefined::<super::JsOption<i32>>.flatten(), Undefined);
+    }
+
+    #[test]
+    fn ok_or_maps_some_to_ok_and_null_or_undefined_to_the_given_err() {
+        assert_eq!(MaybeSome(1).ok_or("missing"), Ok(1));
+        assert_eq!(Null::<i32>.ok_or("missing"), Err("missing"));
+        assert_eq!(Undefined::<i32>.ok_or("missing"), Err("missing"));
+        assert_eq!(Null::<i32>.ok_or_else(|| "missing"), Err("missing"));
+        assert_eq!(Undefined::<i32>.ok_or_else(|| "missing"), Err("missing"));
+    }
+
+    #[test]
+    fn iter_and_into_iter_yield_the_value_only_for_some() {
+        assert_eq!(MaybeSome(1).iter().copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(Null::<i32>.iter().collect::<Vec<_>>(), Vec::<&i32>::new());
+        assert_eq!(Undefined::<i32>.iter().collect::<Vec<_>>(), Vec::<&i32>::new());
+
+        let mut some = MaybeSome(1);
+        for val in some.iter_mut() {
+            *val += 1;
+        }
+        assert_eq!(some, MaybeSome(2));
+
+        assert_eq!(MaybeSome(1).into_iter().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(Null::<i32>.into_iter().collect::<Vec<_>>(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn expect_returns_the_value_on_some() {
+        assert_eq!(MaybeSome(1).expect("should have a value"), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "should have a value")]
+    fn expect_panics_on_null() {
+        Null::<i32>.expect("should have a value");
+    }
+
+    #[test]
+    #[should_panic(expected = "should have a value")]
+    fn expect_panics_on_undefined() {
+        Undefined::<i32>.expect("should have a value");
+    }
+
+    #[test]
+    fn apply_to_sets_on_some_clears_on_null_and_leaves_untouched_on_undefined() {
+        let mut target = Some(1);
+        MaybeSome(2).apply_to(&mut target);
+        assert_eq!(target, Some(2));
+
+        let mut target = Some(1);
+        Null::<i32>.apply_to(&mut target);
+        assert_eq!(target, None);
+
+        let mut target = Some(1);
+        Undefined::<i32>.apply_to(&mut target);
+        assert_eq!(target, Some(1));
+
+        let mut target = None;
+        Undefined::<i32>.apply_to(&mut target);
+        assert_eq!(target, None);
+    }
+
+    #[test]
+    fn merge_is_apply_to_by_value() {
+        assert_eq!(MaybeSome(2).merge(Some(1)), Some(2));
+        assert_eq!(Null::<i32>.merge(Some(1)), None);
+        assert_eq!(Undefined::<i32>.merge(Some(1)), Some(1));
+        assert_eq!(Undefined::<i32>.merge(None), None);
+    }
+}
@@ -0,0 +1,90 @@
+// Undo rename from Cargo.toml
+extern crate serde_crate as serde;
+
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+use crate::JsOption;
+
+/// Deserializes a field typed `Option<Option<T>>` such that a present `null`
+/// becomes `Some(None)` and a present value becomes `Some(Some(v))`.
+///
+/// Pair with `#[serde(default)]` on the field so that a missing field
+/// deserializes to `None` without this function being called at all.
+///
+/// Internally this goes through [`JsOption::from_option`] and
+/// [`JsOption::into_nested_option`] to reuse the same three-state logic as
+/// `JsOption` itself.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(|opt| JsOption::from_option(opt).into_nested_option())
+}
+
+/// Serializes a field typed `Option<Option<T>>`, the counterpart to
+/// [`deserialize`]: `Some(Some(v))` serializes as `v` and `Some(None)` as an
+/// explicit null.
+///
+/// This function is always called with a value, even for the outer `None`
+/// case, so it has no way to omit the field on its own; pair it with
+/// `#[serde(skip_serializing_if = "Option::is_none")]` to actually drop a
+/// missing field from the output instead of serializing it as null.
+pub fn serialize<T, S>(value: &Option<Option<T>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    match value {
+        Some(Some(val)) => serializer.serialize_some(val),
+        Some(None) | None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_crate as serde;
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    #[serde(crate = "serde")]
+    struct Wrapper {
+        #[serde(default, with = "super", skip_serializing_if = "Option::is_none")]
+        field: Option<Option<i32>>,
+    }
+
+    #[test]
+    fn present_null_deserializes_to_some_none() {
+        let w: Wrapper = serde_json::from_str(r#"{"field":null}"#).unwrap();
+        assert_eq!(w, Wrapper { field: Some(None) });
+    }
+
+    #[test]
+    fn present_value_deserializes_to_some_some() {
+        let w: Wrapper = serde_json::from_str(r#"{"field":1}"#).unwrap();
+        assert_eq!(w, Wrapper { field: Some(Some(1)) });
+    }
+
+    #[test]
+    fn missing_field_deserializes_to_none() {
+        let w: Wrapper = serde_json::from_str(r#"{}"#).unwrap();
+        assert_eq!(w, Wrapper { field: None });
+    }
+
+    #[test]
+    fn some_some_serializes_to_the_value_and_some_none_to_null() {
+        let w = Wrapper { field: Some(Some(1)) };
+        assert_eq!(serde_json::to_string(&w).unwrap(), r#"{"field":1}"#);
+
+        let w = Wrapper { field: Some(None) };
+        assert_eq!(serde_json::to_string(&w).unwrap(), r#"{"field":null}"#);
+    }
+
+    #[test]
+    fn outer_none_is_skipped_on_serialize() {
+        let w = Wrapper { field: None };
+        assert_eq!(serde_json::to_string(&w).unwrap(), "{}");
+    }
+}
@@ -0,0 +1,153 @@
+// Undo rename from Cargo.toml
+extern crate serde_crate as serde;
+
+use std::fmt;
+
+use serde::{
+    de::{Deserialize, Deserializer, Error as _},
+    ser::{Error as _, Serialize, Serializer},
+};
+use zvariant::{NoneValue, Signature, Type};
+
+use crate::JsOption;
+
+/// `JsOption<T>` reports the same signature as `T`: D-Bus has no nullability
+/// of its own, so there is no "maybe" marker to add here. [`serialize`] and
+/// [`deserialize`] make this work by mirroring `zvariant::Optional<T>`'s own
+/// convention of writing `T`'s none-sentinel (`T::null_value()`) for an
+/// absent value, rather than the crate's default `serde` impl, which calls
+/// `serialize_none()`/`serialize_some()` and has no valid representation
+/// under a signature that declares an unconditional `T`.
+impl<T: Type> Type for JsOption<T> {
+    fn signature() -> Signature<'static> {
+        T::signature()
+    }
+}
+
+/// Serializes a `JsOption<T>` the way `zvariant::Optional<T>` serializes an
+/// `Option<T>`: `Some(val)` writes `val` itself, and `Null` writes `T`'s
+/// none-sentinel (`T::null_value()`, e.g. an empty string or a zeroed
+/// number). Use via `#[serde(with = "js_option::zvariant")]`.
+///
+/// Unlike `Null`, `Undefined` has no sentinel value of its own to fall back
+/// to without becoming indistinguishable from `Null` on the wire, so
+/// serializing it is a clear error instead of a silent collapse onto the
+/// same representation.
+///
+/// A `Some` holding `T`'s sentinel value (e.g. `Some(String::new())`) is
+/// written exactly like `Null`, so [`deserialize`] reads it back as `Null`.
+///
+/// `bool`'s sentinel (`T::null_value()`) is `false`, one of its only two
+/// meaningful values, so `Some(false)` and `Null` would be indistinguishable on the
+/// wire; as with `zvariant::Optional<bool>`, this is rejected as an error
+/// rather than silently losing that distinction.
+pub fn serialize<T, S>(value: &JsOption<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Type + NoneValue + Serialize,
+    <T as NoneValue>::NoneType: Serialize,
+    S: Serializer,
+{
+    if T::signature() == bool::signature() {
+        return Err(S::Error::custom("`JsOption<bool>` is not supported"));
+    }
+
+    match value {
+        JsOption::Some(val) => val.serialize(serializer),
+        JsOption::Null => T::null_value().serialize(serializer),
+        JsOption::Undefined => Err(S::Error::custom("attempted to serialize `undefined`")),
+    }
+}
+
+/// Deserializes a `JsOption<T>` the way `zvariant::Optional<T>` deserializes
+/// an `Option<T>`: a value equal to `T`'s none-sentinel becomes `Null`, and
+/// any other value is read as `T` and wrapped in `Some`.
+///
+/// This never produces `Undefined`, matching the crate's default
+/// `Deserialize` impl for other formats.
+///
+/// Rejects `T = bool` with an error for the same reason [`serialize`] does:
+/// `bool`'s sentinel value (`false`) is also a meaningful value, so there's
+/// no way to tell `Some(false)` and `Null` apart on the wire.
+pub fn deserialize<'de, T, D, E>(deserializer: D) -> Result<JsOption<T>, D::Error>
+where
+    T: Type + NoneValue,
+    <T as NoneValue>::NoneType: Deserialize<'de> + TryInto<T, Error = E> + PartialEq,
+    E: fmt::Display,
+    D: Deserializer<'de>,
+{
+    if T::signature() == bool::signature() {
+        return Err(D::Error::custom("`JsOption<bool>` is not supported"));
+    }
+
+    let value = <T as NoneValue>::NoneType::deserialize(deserializer)?;
+    if value == T::null_value() {
+        Ok(JsOption::Null)
+    } else {
+        Ok(JsOption::Some(value.try_into().map_err(serde::de::Error::custom)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_crate as serde;
+    use zvariant::{serialized::Context, to_bytes, LE};
+
+    use super::JsOption;
+
+    #[derive(serde::Serialize, serde::Deserialize, zvariant::Type, Debug, PartialEq)]
+    #[serde(crate = "serde")]
+    struct Wrapper {
+        #[serde(with = "super")]
+        field: JsOption<String>,
+    }
+
+    #[test]
+    fn round_trips_some_and_null() {
+        for w in [
+            Wrapper { field: JsOption::Some("hi".to_string()) },
+            Wrapper { field: JsOption::Null },
+        ] {
+            let ctx = Context::new_dbus(LE, 0);
+            let encoded = to_bytes(ctx, &w).unwrap();
+            let (decoded, _): (Wrapper, _) = encoded.deserialize().unwrap();
+            assert_eq!(decoded, w);
+        }
+    }
+
+    #[test]
+    fn undefined_errors_on_serialize() {
+        let ctx = Context::new_dbus(LE, 0);
+        let w = Wrapper { field: JsOption::Undefined };
+        assert!(to_bytes(ctx, &w).is_err());
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, zvariant::Type, Debug, PartialEq)]
+    #[serde(crate = "serde")]
+    struct BoolWrapper {
+        #[serde(with = "super")]
+        field: JsOption<bool>,
+    }
+
+    #[test]
+    fn bool_is_rejected_on_serialize() {
+        let ctx = Context::new_dbus(LE, 0);
+        let some_false = BoolWrapper { field: JsOption::Some(false) };
+        assert!(to_bytes(ctx, &some_false).is_err());
+
+        let null = BoolWrapper { field: JsOption::Null };
+        assert!(to_bytes(ctx, &null).is_err());
+    }
+
+    #[test]
+    fn bool_is_rejected_on_deserialize() {
+        #[derive(serde::Serialize, zvariant::Type)]
+        #[serde(crate = "serde")]
+        struct PlainBoolWrapper {
+            field: bool,
+        }
+
+        let ctx = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctx, &PlainBoolWrapper { field: false }).unwrap();
+        assert!(encoded.deserialize::<BoolWrapper>().is_err());
+    }
+}